@@ -1,9 +1,17 @@
 use num_traits::Float;
 use std::collections::HashSet;
-use std::fmt::Display;
+use std::fmt::{self, Display};
+
+/// Forward function for a [`Op::Custom`] node: computes a node's data from
+/// its parents' data.
+pub type CustomForward<T> = Box<dyn Fn(&[T]) -> T>;
+
+/// Backward function for a [`Op::Custom`] node: maps
+/// `(upstream_grad, parent_data, node_output)` to the gradient contribution
+/// for each parent, in `parents` order.
+pub type CustomBackward<T> = Box<dyn Fn(T, &[T], T) -> Vec<T>>;
 
 /// Operation type for each node in the graph.
-#[derive(Debug)]
 pub enum Op<T> {
     Input,
     Add,
@@ -13,6 +21,54 @@ pub enum Op<T> {
     Relu,
     Tanh,
     Pow(T),
+    /// A user-supplied differentiable primitive: `forward` computes the
+    /// node's data from its parents' data, `backward` maps
+    /// `(upstream_grad, parent_data, node_output)` to the gradient
+    /// contribution for each parent, in the same order as `parents`.
+    Custom {
+        name: &'static str,
+        forward: CustomForward<T>,
+        backward: CustomBackward<T>,
+    },
+}
+
+/// DFS coloring used by [`GraphArena::detect_cycle`].
+#[derive(Clone, Copy, PartialEq)]
+enum Color {
+    White,
+    Gray,
+    Black,
+}
+
+/// Failure returned by [`GraphArena::connect`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConnectError {
+    /// Wiring the edge would make `parent` reachable from itself; the path
+    /// that would close the cycle, as node indices.
+    Cycle(Vec<usize>),
+    /// `child`'s op has a fixed arity (only [`Op::Custom`] accepts a
+    /// variable-length `parents` list) and already has its full set of
+    /// parents, so it cannot accept another edge.
+    FixedArity {
+        op_name: &'static str,
+        max_parents: usize,
+    },
+}
+
+impl<T: fmt::Debug> fmt::Debug for Op<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Op::Input => write!(f, "Input"),
+            Op::Add => write!(f, "Add"),
+            Op::Sub => write!(f, "Sub"),
+            Op::Mul => write!(f, "Mul"),
+            Op::Div => write!(f, "Div"),
+            Op::Relu => write!(f, "Relu"),
+            Op::Tanh => write!(f, "Tanh"),
+            Op::Pow(exp) => f.debug_tuple("Pow").field(exp).finish(),
+            Op::Custom { name, .. } => f.debug_struct("Custom").field("name", name).finish(),
+        }
+    }
 }
 
 /// A single node in the computation graph.
@@ -153,7 +209,129 @@ impl<T: Float + Copy> GraphArena<T> {
         idx
     }
 
+    /// Create a node for a user-supplied differentiable primitive.
+    ///
+    /// `forward` computes the node's data from its parents' current data;
+    /// `backward` maps `(upstream_grad, parent_data, node_output)` to the
+    /// gradient contribution for each parent, in `parents` order. The
+    /// `backward` dispatch below calls the stored closure and accumulates
+    /// its returned per-parent gradients the same way the built-in ops do.
+    pub fn apply(
+        &mut self,
+        name: &'static str,
+        parents: Vec<usize>,
+        forward: impl Fn(&[T]) -> T + 'static,
+        backward: impl Fn(T, &[T], T) -> Vec<T> + 'static,
+    ) -> usize {
+        let operand_data: Vec<T> = parents.iter().map(|&p| self.nodes[p].data).collect();
+        let data = forward(&operand_data);
+        let idx = self.nodes.len();
+        self.nodes.push(Node {
+            data,
+            grad: T::zero(),
+            op: Op::Custom {
+                name,
+                forward: Box::new(forward),
+                backward: Box::new(backward),
+            },
+            parents,
+        });
+        self.topo.push(idx);
+        idx
+    }
+
+    /// Local derivative of `idx`'s op times its own (already accumulated)
+    /// `grad`, one contribution per entry of `self.nodes[idx].parents`, in
+    /// order. Shared by [`GraphArena::backward`] and
+    /// [`GraphArena::backward_checkpointed`] so the two passes can never
+    /// disagree on a gradient formula.
+    fn local_grads(&self, idx: usize, grad: T) -> Vec<T> {
+        let parents = &self.nodes[idx].parents;
+        match &self.nodes[idx].op {
+            Op::Add => {
+                assert_eq!(parents.len(), 2, "Op::Add node {idx} must have exactly 2 parents; connect() only supports extra parent edges on Op::Custom nodes");
+                vec![grad, grad]
+            }
+            Op::Sub => {
+                assert_eq!(parents.len(), 2, "Op::Sub node {idx} must have exactly 2 parents; connect() only supports extra parent edges on Op::Custom nodes");
+                vec![grad, T::zero() - grad]
+            }
+            Op::Mul => {
+                assert_eq!(parents.len(), 2, "Op::Mul node {idx} must have exactly 2 parents; connect() only supports extra parent edges on Op::Custom nodes");
+                let (a, b) = (parents[0], parents[1]);
+                vec![self.nodes[b].data * grad, self.nodes[a].data * grad]
+            }
+            Op::Div => {
+                assert_eq!(parents.len(), 2, "Op::Div node {idx} must have exactly 2 parents; connect() only supports extra parent edges on Op::Custom nodes");
+                let (a, b) = (parents[0], parents[1]);
+                let (ad, bd) = (self.nodes[a].data, self.nodes[b].data);
+                vec![grad / bd, T::zero() - (ad * grad) / (bd * bd)]
+            }
+            Op::Relu => {
+                assert_eq!(parents.len(), 1, "Op::Relu node {idx} must have exactly 1 parent; connect() only supports extra parent edges on Op::Custom nodes");
+                let a = parents[0];
+                if self.nodes[a].data > T::zero() {
+                    vec![grad]
+                } else {
+                    vec![T::zero()]
+                }
+            }
+            Op::Tanh => {
+                assert_eq!(parents.len(), 1, "Op::Tanh node {idx} must have exactly 1 parent; connect() only supports extra parent edges on Op::Custom nodes");
+                let y = self.nodes[idx].data;
+                vec![(T::one() - y * y) * grad]
+            }
+            Op::Pow(exp) => {
+                assert_eq!(parents.len(), 1, "Op::Pow node {idx} must have exactly 1 parent; connect() only supports extra parent edges on Op::Custom nodes");
+                let exp = *exp;
+                let a = parents[0];
+                let x = self.nodes[a].data;
+                vec![exp * x.powf(exp - T::one()) * grad]
+            }
+            Op::Custom { backward, .. } => {
+                let operand_data: Vec<T> = parents.iter().map(|&p| self.nodes[p].data).collect();
+                let output = self.nodes[idx].data;
+                backward(grad, &operand_data, output)
+            }
+            Op::Input => Vec::new(),
+        }
+    }
+
+    /// Recompute a single node's `data` from its parents' current `data`,
+    /// using the same formula its constructor used. Used by
+    /// [`GraphArena::backward_checkpointed`] to restore nodes whose data was
+    /// dropped after the forward pass.
+    fn recompute(&mut self, idx: usize) {
+        let parents = self.nodes[idx].parents.clone();
+        let data = match &self.nodes[idx].op {
+            Op::Input => return,
+            Op::Add => self.nodes[parents[0]].data + self.nodes[parents[1]].data,
+            Op::Sub => self.nodes[parents[0]].data - self.nodes[parents[1]].data,
+            Op::Mul => self.nodes[parents[0]].data * self.nodes[parents[1]].data,
+            Op::Div => self.nodes[parents[0]].data / self.nodes[parents[1]].data,
+            Op::Relu => {
+                let x = self.nodes[parents[0]].data;
+                if x > T::zero() { x } else { T::zero() }
+            }
+            Op::Tanh => self.nodes[parents[0]].data.tanh(),
+            Op::Pow(exp) => {
+                let exp = *exp;
+                self.nodes[parents[0]].data.powf(exp)
+            }
+            Op::Custom { forward, .. } => {
+                let operand_data: Vec<T> = parents.iter().map(|&p| self.nodes[p].data).collect();
+                forward(&operand_data)
+            }
+        };
+        self.nodes[idx].data = data;
+    }
+
     /// Perform backward pass from loss index to compute gradients.
+    ///
+    /// Always re-derives the traversal order via [`GraphArena::topo_sort`]
+    /// rather than trusting the `topo` field, so graphs rewired after
+    /// construction by [`GraphArena::connect`] still get a gradient pass
+    /// consistent with their current edges.
     pub fn backward(&mut self, loss_idx: usize) {
         // Reset grads
         for node in &mut self.nodes {
@@ -163,59 +341,232 @@ impl<T: Float + Copy> GraphArena<T> {
         self.nodes[loss_idx].grad = T::one();
 
         // Traverse in reverse topological order
-        for &idx in self.topo.iter().rev() {
+        for &idx in self.topo_sort().iter().rev() {
             let grad = self.nodes[idx].grad;
-            let parents = &self.nodes[idx].parents;
-            match self.nodes[idx].op {
-                Op::Add => {
-                    let [a, b] = <[usize; 2]>::try_from(parents.clone()).unwrap();
-                    self.nodes[a].grad = self.nodes[a].grad + grad;
-                    self.nodes[b].grad = self.nodes[b].grad + grad;
-                }
-                Op::Sub => {
-                    let [a, b] = <[usize; 2]>::try_from(parents.clone()).unwrap();
-                    self.nodes[a].grad = self.nodes[a].grad + grad;
-                    self.nodes[b].grad = self.nodes[b].grad - grad;
+            let parents = self.nodes[idx].parents.clone();
+            let contributions = self.local_grads(idx, grad);
+            for (&p, contribution) in parents.iter().zip(contributions) {
+                self.nodes[p].grad = self.nodes[p].grad + contribution;
+            }
+        }
+    }
+
+    /// Memory-bounded backward pass: only every `spacing`-th node in
+    /// topological order (plus every `Input` leaf) keeps its forward `data`
+    /// cached; everything else is dropped up front and restored on demand —
+    /// the classic block/√N checkpointing decomposition. A dropped node is
+    /// recomputed, lazily and at most once, the moment the reverse sweep
+    /// first needs it, by replaying its op from its (possibly also just
+    /// restored) parents; it is dropped again as soon as every consumer that
+    /// could still need it has run. Gradients produced are identical to
+    /// [`GraphArena::backward`]; only the live-memory footprint (O(√N)
+    /// cached intermediates instead of O(N)) and total forward work (O(N)
+    /// extra recomputation) differ. Leaves `backward` itself untouched.
+    pub fn backward_checkpointed(&mut self, loss_idx: usize) {
+        let spacing = (self.topo.len() as f64).sqrt().ceil() as usize;
+        self.backward_checkpointed_with_spacing(loss_idx, spacing.max(1));
+    }
+
+    /// Same as [`GraphArena::backward_checkpointed`] but with an explicit
+    /// checkpoint-spacing knob instead of the default `⌈√N⌉`.
+    pub fn backward_checkpointed_with_spacing(&mut self, loss_idx: usize, spacing: usize) {
+        let spacing = spacing.max(1);
+        let topo = self.topo_sort();
+
+        let mut is_checkpoint = vec![false; self.nodes.len()];
+        for (pos, &idx) in topo.iter().enumerate() {
+            is_checkpoint[idx] = matches!(self.nodes[idx].op, Op::Input) || pos % spacing == 0;
+        }
+
+        // Every non-checkpoint node's data is read exactly once by itself
+        // (when its own turn comes in the reverse sweep below) plus once per
+        // distinct node that lists it as a parent. Once that count hits
+        // zero, nothing will ever need its data again and it can be dropped
+        // for good.
+        let mut remaining_uses = vec![1usize; self.nodes.len()];
+        for idx in 0..self.nodes.len() {
+            let mut seen = HashSet::new();
+            for &p in &self.nodes[idx].parents {
+                if seen.insert(p) {
+                    remaining_uses[p] += 1;
                 }
-                Op::Mul => {
-                    let [a, b] = <[usize; 2]>::try_from(parents.clone()).unwrap();
-                    let da = self.nodes[b].data * grad;
-                    let db = self.nodes[a].data * grad;
-                    self.nodes[a].grad = self.nodes[a].grad + da;
-                    self.nodes[b].grad = self.nodes[b].grad + db;
+            }
+        }
+
+        let mut live = is_checkpoint.clone();
+        for (idx, &alive) in live.iter().enumerate() {
+            if !alive {
+                self.nodes[idx].data = T::zero();
+            }
+        }
+
+        for node in &mut self.nodes {
+            node.grad = T::zero();
+        }
+        self.nodes[loss_idx].grad = T::one();
+
+        for &idx in topo.iter().rev() {
+            self.ensure_live(idx, &mut live);
+            let parents = self.nodes[idx].parents.clone();
+            for &p in &parents {
+                self.ensure_live(p, &mut live);
+            }
+
+            let grad = self.nodes[idx].grad;
+            let contributions = self.local_grads(idx, grad);
+            for (&p, contribution) in parents.iter().zip(contributions) {
+                self.nodes[p].grad = self.nodes[p].grad + contribution;
+            }
+
+            remaining_uses[idx] -= 1;
+            if remaining_uses[idx] == 0 && !is_checkpoint[idx] {
+                self.nodes[idx].data = T::zero();
+                live[idx] = false;
+            }
+            let mut seen = HashSet::new();
+            for &p in &parents {
+                if !seen.insert(p) {
+                    continue;
                 }
-                Op::Div => {
-                    let [a, b] = <[usize; 2]>::try_from(parents.clone()).unwrap();
-                    let da = grad / self.nodes[b].data;
-                    let db =
-                        -(self.nodes[a].data * grad) / (self.nodes[b].data * self.nodes[b].data);
-                    self.nodes[a].grad = self.nodes[a].grad + da;
-                    self.nodes[b].grad = self.nodes[b].grad + db;
+                remaining_uses[p] -= 1;
+                if remaining_uses[p] == 0 && !is_checkpoint[p] {
+                    self.nodes[p].data = T::zero();
+                    live[p] = false;
                 }
-                Op::Relu => {
-                    let a = parents[0];
-                    let d = if self.nodes[a].data > T::zero() {
-                        T::one()
-                    } else {
-                        T::zero()
-                    };
-                    self.nodes[a].grad = self.nodes[a].grad + d * grad;
+            }
+        }
+    }
+
+    /// Restore `idx`'s dropped `data` by recomputing it from its parents,
+    /// recursing to restore any parent whose own data was dropped first.
+    /// A no-op once `idx` is already live; every node is recomputed at most
+    /// once per [`GraphArena::backward_checkpointed_with_spacing`] call.
+    fn ensure_live(&mut self, idx: usize, live: &mut [bool]) {
+        if live[idx] {
+            return;
+        }
+        let parents = self.nodes[idx].parents.clone();
+        for &p in &parents {
+            self.ensure_live(p, live);
+        }
+        self.recompute(idx);
+        live[idx] = true;
+    }
+
+    /// Recompute a valid topological order by post-order DFS over `parents`
+    /// edges (a node is pushed after all its parents, i.e. nodes appear in
+    /// DFS finishing order, parents first). Unlike the `topo` field, which only
+    /// stays valid because every constructor appends after its parents
+    /// already exist, this works for any wiring, including edges added by
+    /// [`GraphArena::connect`].
+    pub fn topo_sort(&self) -> Vec<usize> {
+        let mut order = Vec::with_capacity(self.nodes.len());
+        let mut visited = vec![false; self.nodes.len()];
+        for start in 0..self.nodes.len() {
+            if !visited[start] {
+                self.topo_visit(start, &mut visited, &mut order);
+            }
+        }
+        order
+    }
+
+    fn topo_visit(&self, idx: usize, visited: &mut [bool], order: &mut Vec<usize>) {
+        visited[idx] = true;
+        for &p in &self.nodes[idx].parents {
+            if !visited[p] {
+                self.topo_visit(p, visited, order);
+            }
+        }
+        order.push(idx);
+    }
+
+    /// Three-color (white/gray/black) DFS cycle check over `parents` edges.
+    /// Returns the offending cycle, as a path of node indices, if the graph
+    /// is not a DAG.
+    pub fn detect_cycle(&self) -> Result<(), Vec<usize>> {
+        let mut color = vec![Color::White; self.nodes.len()];
+        let mut stack = Vec::new();
+        for start in 0..self.nodes.len() {
+            if color[start] == Color::White {
+                if let Some(cycle) = self.detect_cycle_visit(start, &mut color, &mut stack) {
+                    return Err(cycle);
                 }
-                Op::Tanh => {
-                    let a = parents[0];
-                    let y = self.nodes[idx].data;
-                    let d = T::one() - y * y;
-                    self.nodes[a].grad = self.nodes[a].grad + d * grad;
+            }
+        }
+        Ok(())
+    }
+
+    fn detect_cycle_visit(
+        &self,
+        idx: usize,
+        color: &mut [Color],
+        stack: &mut Vec<usize>,
+    ) -> Option<Vec<usize>> {
+        color[idx] = Color::Gray;
+        stack.push(idx);
+        for &p in &self.nodes[idx].parents {
+            match color[p] {
+                Color::Gray => {
+                    let start = stack.iter().position(|&n| n == p).unwrap();
+                    return Some(stack[start..].to_vec());
                 }
-                Op::Pow(exp) => {
-                    let a = parents[0];
-                    let x = self.nodes[a].data;
-                    let d = exp * x.powf(exp - T::one());
-                    self.nodes[a].grad = self.nodes[a].grad + d * grad;
+                Color::White => {
+                    if let Some(cycle) = self.detect_cycle_visit(p, color, stack) {
+                        return Some(cycle);
+                    }
                 }
-                Op::Input => {}
+                Color::Black => {}
             }
         }
+        stack.pop();
+        color[idx] = Color::Black;
+        None
+    }
+
+    /// `(name, max parents)` for `op`. Only [`Op::Custom`] accepts a
+    /// variable-length `parents` list in its gradient formula; every other
+    /// op's arity is fixed by how many operands its forward formula takes.
+    /// Shared by [`GraphArena::connect`] to reject a parent edge a node's op
+    /// can't make use of.
+    fn op_arity(op: &Op<T>) -> (&'static str, usize) {
+        match op {
+            Op::Input => ("Input", 0),
+            Op::Add => ("Add", 2),
+            Op::Sub => ("Sub", 2),
+            Op::Mul => ("Mul", 2),
+            Op::Div => ("Div", 2),
+            Op::Relu => ("Relu", 1),
+            Op::Tanh => ("Tanh", 1),
+            Op::Pow(_) => ("Pow", 1),
+            Op::Custom { .. } => ("Custom", usize::MAX),
+        }
+    }
+
+    /// Wire an additional parent edge onto an existing node, e.g. to tie a
+    /// recurrent output back into an earlier node. Rejected if `child`'s op
+    /// is already at its fixed arity (only [`Op::Custom`] accepts a
+    /// variable-length `parents` list), or if `parent` is reachable from
+    /// `child` (with the cycle that would result).
+    ///
+    /// Re-derives `self.topo` via [`GraphArena::topo_sort`] on success, since
+    /// insertion order no longer reflects a valid topological order once
+    /// edges have been added out of construction order.
+    pub fn connect(&mut self, child: usize, parent: usize) -> Result<(), ConnectError> {
+        let (op_name, max_parents) = Self::op_arity(&self.nodes[child].op);
+        if self.nodes[child].parents.len() >= max_parents {
+            return Err(ConnectError::FixedArity {
+                op_name,
+                max_parents,
+            });
+        }
+
+        self.nodes[child].parents.push(parent);
+        if let Err(cycle) = self.detect_cycle() {
+            self.nodes[child].parents.pop();
+            return Err(ConnectError::Cycle(cycle));
+        }
+        self.topo = self.topo_sort();
+        Ok(())
     }
 }
 
@@ -242,7 +593,7 @@ impl<T: Float + Copy + Display> GraphArena<T> {
 
         // Node details
         let node = &self.nodes[idx];
-        let op_str = match node.op {
+        let op_str = match &node.op {
             Op::Add => "+",
             Op::Sub => "-",
             Op::Mul => "*",
@@ -251,6 +602,7 @@ impl<T: Float + Copy + Display> GraphArena<T> {
             Op::Tanh => "tanh",
             Op::Pow(_) => "pow",
             Op::Input => "input",
+            Op::Custom { name, .. } => name,
         };
         println!(
             "{}: {:.4} ({}) [grad={:.4}]",
@@ -3,7 +3,7 @@ use std::collections::HashSet;
 use std::{
     cell::RefCell,
     fmt::{Debug, Display},
-    ops::{Add, AddAssign, Mul, MulAssign},
+    ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub},
     rc::{Rc, Weak},
 };
 
@@ -12,6 +12,8 @@ struct Inner<T: Float + Copy> {
     data: T,
     grad: T,
     oper: char,
+    /// Exponent operand for `powf` nodes (`oper == 'p'`); unused otherwise.
+    exponent: T,
     label: String,
     prev: Vec<Weak<RefCell<Inner<T>>>>,
 }
@@ -31,6 +33,7 @@ where
                 data,
                 grad: T::zero(),
                 oper: '\0',
+                exponent: T::zero(),
                 label: String::new(),
                 prev: Vec::new(),
             })),
@@ -43,6 +46,20 @@ where
                 data,
                 grad: T::zero(),
                 oper,
+                exponent: T::zero(),
+                label: String::new(),
+                prev: Vec::new(),
+            })),
+        }
+    }
+
+    fn new_with_pow(data: T, exponent: T) -> Self {
+        Value {
+            inner: Rc::new(RefCell::new(Inner {
+                data,
+                grad: T::zero(),
+                oper: 'p',
+                exponent,
                 label: String::new(),
                 prev: Vec::new(),
             })),
@@ -69,6 +86,122 @@ where
         let weak_child = Rc::downgrade(&child.inner);
         self.inner.borrow_mut().prev.push(weak_child);
     }
+
+    /// Run reverse-mode autodiff over the graph reachable from `self`.
+    ///
+    /// Builds a post-order topological order over `prev` edges (a node is
+    /// only pushed once all of its operands have been visited), zeroes every
+    /// reachable node's grad, seeds `self.grad = 1`, then walks the order in
+    /// reverse accumulating each node's local derivative times its own grad
+    /// into its operands. The visited set is keyed on `Rc::as_ptr` so shared
+    /// subgraphs are only queued once and cycles can't cause infinite
+    /// recursion.
+    pub fn backward(&self) {
+        let mut topo: Vec<Rc<RefCell<Inner<T>>>> = Vec::new();
+        let mut visited: HashSet<*const RefCell<Inner<T>>> = HashSet::new();
+        Self::build_topo(&self.inner, &mut visited, &mut topo);
+
+        for node in &topo {
+            node.borrow_mut().grad = T::zero();
+        }
+        self.inner.borrow_mut().grad = T::one();
+
+        for node in topo.iter().rev() {
+            let (oper, exponent, grad, data) = {
+                let inner = node.borrow();
+                (inner.oper, inner.exponent, inner.grad, inner.data)
+            };
+            let prev: Vec<Rc<RefCell<Inner<T>>>> = node
+                .borrow()
+                .prev
+                .iter()
+                .filter_map(Weak::upgrade)
+                .collect();
+
+            match oper {
+                '+' => {
+                    for p in &prev {
+                        let g = p.borrow().grad;
+                        p.borrow_mut().grad = g + grad;
+                    }
+                }
+                '-' => {
+                    if let [a, b] = prev.as_slice() {
+                        let ga = a.borrow().grad;
+                        a.borrow_mut().grad = ga + grad;
+                        let gb = b.borrow().grad;
+                        b.borrow_mut().grad = gb - grad;
+                    }
+                }
+                '*' => {
+                    if let [a, b] = prev.as_slice() {
+                        let (a_data, b_data) = (a.borrow().data, b.borrow().data);
+                        let ga = a.borrow().grad;
+                        a.borrow_mut().grad = ga + b_data * grad;
+                        let gb = b.borrow().grad;
+                        b.borrow_mut().grad = gb + a_data * grad;
+                    }
+                }
+                '/' => {
+                    if let [a, b] = prev.as_slice() {
+                        let (a_data, b_data) = (a.borrow().data, b.borrow().data);
+                        let ga = a.borrow().grad;
+                        a.borrow_mut().grad = ga + grad / b_data;
+                        let gb = b.borrow().grad;
+                        b.borrow_mut().grad = gb - a_data * grad / (b_data * b_data);
+                    }
+                }
+                'n' => {
+                    if let [a] = prev.as_slice() {
+                        let ga = a.borrow().grad;
+                        a.borrow_mut().grad = ga - grad;
+                    }
+                }
+                'r' => {
+                    if let [a] = prev.as_slice() {
+                        if a.borrow().data > T::zero() {
+                            let ga = a.borrow().grad;
+                            a.borrow_mut().grad = ga + grad;
+                        }
+                    }
+                }
+                't' => {
+                    if let [a] = prev.as_slice() {
+                        let ga = a.borrow().grad;
+                        a.borrow_mut().grad = ga + (T::one() - data * data) * grad;
+                    }
+                }
+                'p' => {
+                    if let [a] = prev.as_slice() {
+                        let (x, ga) = (a.borrow().data, a.borrow().grad);
+                        a.borrow_mut().grad =
+                            ga + exponent * x.powf(exponent - T::one()) * grad;
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn build_topo(
+        node: &Rc<RefCell<Inner<T>>>,
+        visited: &mut HashSet<*const RefCell<Inner<T>>>,
+        topo: &mut Vec<Rc<RefCell<Inner<T>>>>,
+    ) {
+        if !visited.insert(Rc::as_ptr(node)) {
+            return;
+        }
+        let prev: Vec<Rc<RefCell<Inner<T>>>> = node
+            .borrow()
+            .prev
+            .iter()
+            .filter_map(Weak::upgrade)
+            .collect();
+        for p in &prev {
+            Self::build_topo(p, visited, topo);
+        }
+        topo.push(node.clone());
+    }
 }
 
 impl<T> Display for Value<T>
@@ -165,6 +298,113 @@ where
     }
 }
 
+impl<T> Sub<Value<T>> for Value<T>
+where
+    T: Float + Copy + Sub<Output = T>,
+{
+    type Output = Value<T>;
+
+    fn sub(self, other: Value<T>) -> Self::Output {
+        (&self).sub(&other)
+    }
+}
+
+impl<'a, 'b, T> Sub<&'b Value<T>> for &'a Value<T>
+where
+    T: Float + Copy + Sub<Output = T>,
+{
+    type Output = Value<T>;
+
+    fn sub(self, other: &'b Value<T>) -> Value<T> {
+        let new_data = self.get() - other.get();
+        let result = Value::new_with_oper(new_data, '-');
+        result.add_prev(self);
+        result.add_prev(other);
+        result
+    }
+}
+
+impl<T> Div<Value<T>> for Value<T>
+where
+    T: Float + Copy + Div<Output = T>,
+{
+    type Output = Value<T>;
+
+    fn div(self, other: Value<T>) -> Self::Output {
+        (&self).div(&other)
+    }
+}
+
+impl<'a, 'b, T> Div<&'b Value<T>> for &'a Value<T>
+where
+    T: Float + Copy + Div<Output = T>,
+{
+    type Output = Value<T>;
+
+    fn div(self, other: &'b Value<T>) -> Value<T> {
+        let new_data = self.get() / other.get();
+        let result = Value::new_with_oper(new_data, '/');
+        result.add_prev(self);
+        result.add_prev(other);
+        result
+    }
+}
+
+impl<T> Neg for Value<T>
+where
+    T: Float + Copy + Neg<Output = T>,
+{
+    type Output = Value<T>;
+
+    fn neg(self) -> Self::Output {
+        (&self).neg()
+    }
+}
+
+impl<'a, T> Neg for &'a Value<T>
+where
+    T: Float + Copy + Neg<Output = T>,
+{
+    type Output = Value<T>;
+
+    fn neg(self) -> Value<T> {
+        let new_data = -self.get();
+        let result = Value::new_with_oper(new_data, 'n');
+        result.add_prev(self);
+        result
+    }
+}
+
+impl<T> Value<T>
+where
+    T: Float + Copy,
+{
+    /// ReLU activation: `max(0, self)`.
+    pub fn relu(&self) -> Value<T> {
+        let x = self.get();
+        let new_data = if x > T::zero() { x } else { T::zero() };
+        let result = Value::new_with_oper(new_data, 'r');
+        result.add_prev(self);
+        result
+    }
+
+    /// Hyperbolic tangent activation.
+    pub fn tanh(&self) -> Value<T> {
+        let new_data = self.get().tanh();
+        let result = Value::new_with_oper(new_data, 't');
+        result.add_prev(self);
+        result
+    }
+
+    /// Raise to a constant power: `self.powf(exponent)`.
+    pub fn powf(&self, exponent: T) -> Value<T> {
+        let new_data = self.get().powf(exponent);
+        let result = Value::new_with_pow(new_data, exponent);
+        result.add_prev(self);
+        result
+    }
+}
+
 impl<T> AddAssign<Value<T>> for Value<T>
 where
     T: Float + Copy + AddAssign,
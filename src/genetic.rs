@@ -0,0 +1,141 @@
+use crate::arena::GraphArena;
+use crate::mlp::{Module, MLP};
+use num_traits::Float;
+use rand::Rng;
+use rand_distr::{Distribution, Normal};
+
+impl<T: Float + Copy> MLP<T> {
+    /// Flatten every weight and bias into a single gene vector, in the same
+    /// traversal order as [`Module::parameters`] (layer, then neuron, then
+    /// weights-then-bias). Used by [`evolve`] to cross and mutate networks
+    /// without gradients.
+    pub fn chromosome(&self, arena: &GraphArena<T>) -> Vec<T> {
+        self.parameters()
+            .iter()
+            .map(|&id| arena.nodes[id].data)
+            .collect()
+    }
+
+    /// Write a gene vector produced by [`MLP::chromosome`] back into this
+    /// network's existing persistent nodes, in place.
+    pub fn from_chromosome(&mut self, arena: &mut GraphArena<T>, genes: &[T]) {
+        for (&id, &gene) in self.parameters().iter().zip(genes) {
+            arena.nodes[id].data = gene;
+        }
+    }
+}
+
+/// Fitness-proportionate ("roulette wheel") selection: picks an index with
+/// probability proportional to its fitness. Falls back to a uniform pick if
+/// every fitness is zero or negative (a zero-sum population can't be
+/// weighted).
+pub fn roulette<T: Float + Copy>(fitnesses: &[T], rng: &mut impl Rng) -> usize {
+    let total = fitnesses.iter().fold(T::zero(), |acc, &f| acc + f);
+    if total <= T::zero() {
+        return rng.gen_range(0..fitnesses.len());
+    }
+    let pick = T::from(rng.gen::<f64>()).expect("random fraction fits in T") * total;
+    let mut acc = T::zero();
+    for (i, &f) in fitnesses.iter().enumerate() {
+        acc = acc + f;
+        if acc >= pick {
+            return i;
+        }
+    }
+    fitnesses.len() - 1
+}
+
+/// Tournament selection: the fittest of `k` uniformly-sampled candidates.
+pub fn tournament<T: Float + Copy>(fitnesses: &[T], k: usize, rng: &mut impl Rng) -> usize {
+    let mut best = rng.gen_range(0..fitnesses.len());
+    for _ in 1..k {
+        let candidate = rng.gen_range(0..fitnesses.len());
+        if fitnesses[candidate] > fitnesses[best] {
+            best = candidate;
+        }
+    }
+    best
+}
+
+/// Swap each gene between two parent chromosomes with probability 0.5,
+/// producing two complementary children.
+pub fn uniform_crossover<T: Float + Copy>(
+    a: &[T],
+    b: &[T],
+    rng: &mut impl Rng,
+) -> (Vec<T>, Vec<T>) {
+    assert_eq!(
+        a.len(),
+        b.len(),
+        "uniform_crossover: chromosomes must have the same length"
+    );
+    let mut child_a = Vec::with_capacity(a.len());
+    let mut child_b = Vec::with_capacity(a.len());
+    for (&ga, &gb) in a.iter().zip(b) {
+        if rng.gen::<bool>() {
+            child_a.push(ga);
+            child_b.push(gb);
+        } else {
+            child_a.push(gb);
+            child_b.push(ga);
+        }
+    }
+    (child_a, child_b)
+}
+
+/// For each gene, with probability `rate`, add a sample from
+/// `Normal(0, stddev)`.
+pub fn gaussian_mutation<T: Float + Copy>(
+    genes: &mut [T],
+    rate: f64,
+    stddev: T,
+    rng: &mut impl Rng,
+) {
+    let stddev = stddev.to_f64().expect("mutation stddev fits in f64");
+    let normal = Normal::new(0.0, stddev).expect("mutation stddev must be finite and >= 0");
+    for gene in genes.iter_mut() {
+        if rng.gen::<f64>() < rate {
+            let delta = T::from(normal.sample(rng)).expect("mutation delta fits in T");
+            *gene = *gene + delta;
+        }
+    }
+}
+
+/// Evolve one generation of a fixed-topology population in place: select
+/// parents proportional to fitness, cross their chromosomes, mutate the
+/// offspring, then write each child's genes back into an existing member's
+/// persistent weight/bias nodes via [`MLP::from_chromosome`]. Every member of
+/// `population` therefore keeps the same node ids generation over
+/// generation, so evolving for many generations (the feature's own use case:
+/// hundreds of generations of a control task) does not grow `arena.nodes` by
+/// a full population's worth of fresh nodes every time. Population size is
+/// preserved; every member's genes are overwritten, including members that
+/// happened to be selected as a parent this round.
+pub fn evolve<T: Float + Copy>(
+    population: &mut [MLP<T>],
+    arena: &mut GraphArena<T>,
+    fitnesses: &[T],
+    mutation_rate: f64,
+    mutation_stddev: T,
+    rng: &mut impl Rng,
+) {
+    let chromosomes: Vec<Vec<T>> = population.iter().map(|m| m.chromosome(arena)).collect();
+    let mut next_genes = Vec::with_capacity(population.len());
+
+    while next_genes.len() < population.len() {
+        let i = roulette(fitnesses, rng);
+        let j = roulette(fitnesses, rng);
+        let (mut genes_a, mut genes_b) = uniform_crossover(&chromosomes[i], &chromosomes[j], rng);
+        gaussian_mutation(&mut genes_a, mutation_rate, mutation_stddev, rng);
+        next_genes.push(genes_a);
+
+        if next_genes.len() < population.len() {
+            gaussian_mutation(&mut genes_b, mutation_rate, mutation_stddev, rng);
+            next_genes.push(genes_b);
+        }
+    }
+
+    for (member, genes) in population.iter_mut().zip(next_genes) {
+        member.from_chromosome(arena, &genes);
+    }
+}
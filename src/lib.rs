@@ -4,8 +4,15 @@ pub use engine::Value;
 pub mod arena;
 pub use arena::GraphArena;
 
+pub mod tensor;
+pub use tensor::Tensor;
+
 pub mod mlp;
 pub use mlp::{Layer, MLP, Module, Neuron};
 
+pub mod optim;
+
+pub mod genetic;
+
 #[cfg(test)]
 pub mod tests;
@@ -1,10 +1,15 @@
 use rusty_micrograd::GraphArena;
-use rusty_micrograd::mlp::{Layer, MLP, Neuron};
+use rusty_micrograd::mlp::{Activation, Layer, Module, Neuron, MLP};
 
 fn main() {
     let mut arena = GraphArena::<f32>::new();
     let x_ids = vec![arena.input(0.5_f32), arena.input(-1.2_f32)];
-    let neuron = Neuron::new(vec![0.8_f32, -0.4_f32], 0.1_f32, GraphArena::tanh);
+    let neuron = Neuron::new(
+        &mut arena,
+        vec![0.8_f32, -0.4_f32],
+        0.1_f32,
+        Activation::Tanh,
+    );
 
     let layer = Layer::new(vec![neuron]);
     let mut mlp = MLP::new(vec![layer]);
@@ -1,44 +1,195 @@
 use crate::arena::GraphArena;
+use crate::tensor::Tensor;
 use num_traits::Float;
+use rand::Rng;
+use rand_distr::{Distribution, Normal, Uniform};
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
 
 /// A high-level Module trait: anything that can forward through the graph.
 pub trait Module<T: Float + Copy> {
     fn forward(&mut self, arena: &mut GraphArena<T>, inputs: &[usize]) -> Vec<usize>;
+
+    /// Node IDs of every persistent weight/bias this module owns, in a
+    /// stable order, for handing to an [`crate::optim::Optimizer`].
+    fn parameters(&self) -> Vec<usize>;
+
+    /// Forward every row of `batch` through this same module, one output
+    /// row per input row. Since `forward` always reuses this module's
+    /// persistent weight/bias nodes (see [`Neuron::new`]) rather than
+    /// reallocating them, calling it once per row already shares parameters
+    /// across the whole batch instead of duplicating them.
+    fn forward_batch(&mut self, arena: &mut GraphArena<T>, batch: &[Vec<usize>]) -> Vec<Vec<usize>> {
+        batch.iter().map(|inputs| self.forward(arena, inputs)).collect()
+    }
+}
+
+/// A neuron's nonlinearity, inspectable and serializable unlike a bare `fn`
+/// pointer. [`Activation::apply`] builds the graph ops for every variant
+/// except [`Activation::Softmax`], which needs the whole layer's
+/// pre-activation row and is instead applied by [`Layer::forward`].
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Activation<T> {
+    Tanh,
+    Sigmoid,
+    Relu,
+    LeakyRelu(T),
+    Linear,
+    Softmax,
 }
 
-/// Activation function type that operates on node IDs within the graph.
-pub type Activation<T> = fn(&mut GraphArena<T>, usize) -> usize;
+impl<T: Float + Copy> Activation<T> {
+    /// Build the graph ops implementing this activation on node `id`.
+    /// `Softmax` is a no-op here (identity); see [`Layer::forward`].
+    pub fn apply(&self, arena: &mut GraphArena<T>, id: usize) -> usize {
+        match self {
+            Activation::Tanh => arena.tanh(id),
+            Activation::Relu => arena.relu(id),
+            Activation::Linear | Activation::Softmax => id,
+            Activation::LeakyRelu(alpha) => {
+                // leaky_relu(x) = relu(x) - alpha*relu(-x), built from the
+                // arena's existing relu/sub/mul primitives.
+                let pos = arena.relu(id);
+                let zero = arena.input(T::zero());
+                let neg_x = arena.sub(zero, id);
+                let neg = arena.relu(neg_x);
+                let alpha_id = arena.input(*alpha);
+                let scaled_neg = arena.mul(alpha_id, neg);
+                arena.sub(pos, scaled_neg)
+            }
+            Activation::Sigmoid => {
+                // sigmoid(x) = (tanh(x/2) + 1) / 2, built from the arena's
+                // existing tanh/div/add primitives (no exp op needed).
+                let two = arena.input(T::from(2.0).expect("2 fits in T"));
+                let half_x = arena.div(id, two);
+                let t = arena.tanh(half_x);
+                let one = arena.input(T::one());
+                let shifted = arena.add(t, one);
+                arena.div(shifted, two)
+            }
+        }
+    }
+}
 
 /// A single neuron: weighted sum + bias + activation via graph operations.
+///
+/// Weights and bias are allocated once as persistent input nodes in the
+/// arena (see [`Neuron::new`]), so the same node IDs are reused on every
+/// `forward` call and gradients accumulated on them by `backward` can be fed
+/// straight back into an [`crate::optim::Optimizer`].
 pub struct Neuron<T: Float + Copy> {
-    pub weights: Vec<T>,
-    pub bias: T,
+    pub weight_ids: Vec<usize>,
+    pub bias_id: usize,
     pub activation: Activation<T>,
 }
 
 impl<T: Float + Copy> Neuron<T> {
-    pub fn new(weights: Vec<T>, bias: T, activation: Activation<T>) -> Self {
+    /// Allocate `weights` and `bias` as persistent input nodes in `arena`.
+    pub fn new(arena: &mut GraphArena<T>, weights: Vec<T>, bias: T, activation: Activation<T>) -> Self {
+        let weight_ids = weights.into_iter().map(|w| arena.input(w)).collect();
+        let bias_id = arena.input(bias);
         Neuron {
-            weights,
-            bias,
+            weight_ids,
+            bias_id,
             activation,
         }
     }
+
+    /// Snapshot this neuron's current weights, bias, and activation out of
+    /// `arena`, as a plain value that can be serialized (see
+    /// [`NeuronData`]) independent of any particular arena's node IDs.
+    pub fn to_data(&self, arena: &GraphArena<T>) -> NeuronData<T> {
+        NeuronData {
+            weights: self
+                .weight_ids
+                .iter()
+                .map(|&id| arena.nodes[id].data)
+                .collect(),
+            bias: arena.nodes[self.bias_id].data,
+            activation: self.activation,
+        }
+    }
+
+    /// Rebuild a neuron from a snapshot, allocating fresh persistent nodes
+    /// in `arena`.
+    pub fn from_data(arena: &mut GraphArena<T>, data: &NeuronData<T>) -> Self {
+        Neuron::new(arena, data.weights.clone(), data.bias, data.activation)
+    }
+
+    /// Randomly initialize `in_dim` weights and a zero bias for a neuron in
+    /// a layer with `out_dim` neurons total, using Xavier/Glorot
+    /// (tanh/sigmoid/linear/softmax) or He (relu/leaky relu) initialization
+    /// depending on `activation`.
+    pub fn random(
+        arena: &mut GraphArena<T>,
+        in_dim: usize,
+        out_dim: usize,
+        activation: Activation<T>,
+        rng: &mut impl Rng,
+    ) -> Self {
+        let weights = sample_weights(in_dim, out_dim, &activation, rng);
+        Neuron::new(arena, weights, T::zero(), activation)
+    }
+}
+
+/// Draw `fan_in` initial weights for a neuron: He (`Normal(0, sqrt(2/fan_in))`)
+/// for ReLU-family activations, Xavier/Glorot (uniform in
+/// `±sqrt(6/(fan_in+fan_out))`) otherwise.
+fn sample_weights<T: Float + Copy>(
+    fan_in: usize,
+    fan_out: usize,
+    activation: &Activation<T>,
+    rng: &mut impl Rng,
+) -> Vec<T> {
+    match activation {
+        Activation::Relu | Activation::LeakyRelu(_) => {
+            let std = (2.0 / fan_in as f64).sqrt();
+            let normal = Normal::new(0.0, std).expect("He stddev is finite and non-negative");
+            (0..fan_in)
+                .map(|_| T::from(normal.sample(rng)).expect("He sample fits in T"))
+                .collect()
+        }
+        Activation::Tanh | Activation::Sigmoid | Activation::Linear | Activation::Softmax => {
+            let limit = (6.0 / (fan_in + fan_out) as f64).sqrt();
+            let uniform = Uniform::new(-limit, limit);
+            (0..fan_in)
+                .map(|_| T::from(uniform.sample(rng)).expect("Xavier sample fits in T"))
+                .collect()
+        }
+    }
+}
+
+/// A serializable snapshot of a [`Neuron`]'s weights, bias, and activation,
+/// independent of any particular `GraphArena`'s node IDs. Behind the
+/// `serialize` feature flag so a trained network can be checkpointed to
+/// JSON and reloaded without re-deriving the graph topology by hand.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct NeuronData<T> {
+    pub weights: Vec<T>,
+    pub bias: T,
+    pub activation: Activation<T>,
 }
 
 impl<T: Float + Copy> Module<T> for Neuron<T> {
     fn forward(&mut self, arena: &mut GraphArena<T>, inputs: &[usize]) -> Vec<usize> {
         // weighted sum node
-        let mut sum_id = arena.input(self.bias);
-        for (&inp, &w) in inputs.iter().zip(self.weights.iter()) {
-            let w_id = arena.input(w);
+        let mut sum_id = self.bias_id;
+        for (&inp, &w_id) in inputs.iter().zip(self.weight_ids.iter()) {
             let prod_id = arena.mul(inp, w_id);
             sum_id = arena.add(sum_id, prod_id);
         }
-        // apply activation operation in graph
-        let out_id = (self.activation)(arena, sum_id);
+        // apply activation operation in graph (Softmax is deferred to Layer)
+        let out_id = self.activation.apply(arena, sum_id);
         vec![out_id]
     }
+
+    fn parameters(&self) -> Vec<usize> {
+        let mut ids = self.weight_ids.clone();
+        ids.push(self.bias_id);
+        ids
+    }
 }
 
 /// A layer: a collection of neurons.
@@ -50,14 +201,113 @@ impl<T: Float + Copy> Layer<T> {
     pub fn new(neurons: Vec<Neuron<T>>) -> Self {
         Layer { neurons }
     }
+
+    /// Snapshot every neuron in this layer out of `arena`.
+    pub fn to_data(&self, arena: &GraphArena<T>) -> LayerData<T> {
+        LayerData {
+            neurons: self.neurons.iter().map(|n| n.to_data(arena)).collect(),
+        }
+    }
+
+    /// Rebuild a layer from a snapshot, allocating fresh persistent nodes in
+    /// `arena`.
+    pub fn from_data(arena: &mut GraphArena<T>, data: &LayerData<T>) -> Self {
+        let neurons = data
+            .neurons
+            .iter()
+            .map(|n| Neuron::from_data(arena, n))
+            .collect();
+        Layer { neurons }
+    }
+
+    /// Build a layer of `out_dim` randomly-initialized neurons, each taking
+    /// `in_dim` inputs and using `activation` (see [`Neuron::random`]).
+    pub fn random(
+        arena: &mut GraphArena<T>,
+        in_dim: usize,
+        out_dim: usize,
+        activation: Activation<T>,
+        rng: &mut impl Rng,
+    ) -> Self {
+        let neurons = (0..out_dim)
+            .map(|_| Neuron::random(arena, in_dim, out_dim, activation, rng))
+            .collect();
+        Layer::new(neurons)
+    }
+
+    /// Softmax a row of pre-activation outputs jointly: `e^x_i / sum(e^x_j)`.
+    /// `exp` has no dedicated op, so it's built once via `Op::Custom`.
+    fn softmax_row(arena: &mut GraphArena<T>, raw: &[usize]) -> Vec<usize> {
+        let exps: Vec<usize> = raw
+            .iter()
+            .map(|&id| {
+                arena.apply(
+                    "exp",
+                    vec![id],
+                    |xs| xs[0].exp(),
+                    |grad, _xs, output| vec![grad * output],
+                )
+            })
+            .collect();
+        let mut sum = exps[0];
+        for &e in &exps[1..] {
+            sum = arena.add(sum, e);
+        }
+        exps.iter().map(|&e| arena.div(e, sum)).collect()
+    }
+}
+
+/// A serializable snapshot of a [`Layer`]; see [`NeuronData`].
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct LayerData<T> {
+    pub neurons: Vec<NeuronData<T>>,
 }
 
 impl<T: Float + Copy> Module<T> for Layer<T> {
+    /// Express the affine part of the layer (weighted sum + bias, before
+    /// activation) as one `matmul` + broadcast `add` over the neurons'
+    /// existing persistent weight/bias nodes, via [`Tensor::from_ids`],
+    /// rather than a scalar `mul`/`add` loop per neuron. Activations are
+    /// still applied per-neuron since neurons in the same layer may carry
+    /// different [`Activation`]s.
     fn forward(&mut self, arena: &mut GraphArena<T>, inputs: &[usize]) -> Vec<usize> {
-        self.neurons
-            .iter_mut()
-            .flat_map(|n| n.forward(arena, inputs))
-            .collect()
+        let in_dim = inputs.len();
+        let out_dim = self.neurons.len();
+        let weight_ids: Vec<usize> = self
+            .neurons
+            .iter()
+            .flat_map(|n| n.weight_ids.iter().copied())
+            .collect();
+        let weights = Tensor::from_ids((out_dim, in_dim), weight_ids);
+        let bias_ids: Vec<usize> = self.neurons.iter().map(|n| n.bias_id).collect();
+        let bias = Tensor::from_ids((1, out_dim), bias_ids);
+        let input_row = Tensor::from_ids((1, in_dim), inputs.to_vec());
+
+        let pre = input_row
+            .matmul(arena, &weights.transpose())
+            .add(arena, &bias);
+
+        let raw: Vec<usize> = pre
+            .ids
+            .iter()
+            .zip(&self.neurons)
+            .map(|(&id, n)| n.activation.apply(arena, id))
+            .collect();
+
+        let is_softmax = self
+            .neurons
+            .first()
+            .is_some_and(|n| n.activation == Activation::Softmax);
+        if is_softmax {
+            Layer::softmax_row(arena, &raw)
+        } else {
+            raw
+        }
+    }
+
+    fn parameters(&self) -> Vec<usize> {
+        self.neurons.iter().flat_map(|n| n.parameters()).collect()
     }
 }
 
@@ -70,11 +320,60 @@ impl<T: Float + Copy> MLP<T> {
     pub fn new(layers: Vec<Layer<T>>) -> Self {
         MLP { layers }
     }
-    pub fn forward(&mut self, arena: &mut GraphArena<T>, inputs: &[usize]) -> Vec<usize> {
+
+    /// Snapshot the whole network's weights, biases, and activations out of
+    /// `arena`, e.g. to write to disk with `serde_json::to_writer`.
+    pub fn to_data(&self, arena: &GraphArena<T>) -> MlpData<T> {
+        MlpData {
+            layers: self.layers.iter().map(|l| l.to_data(arena)).collect(),
+        }
+    }
+
+    /// Rebuild a network from a snapshot, allocating fresh persistent nodes
+    /// in `arena`.
+    pub fn from_data(arena: &mut GraphArena<T>, data: &MlpData<T>) -> Self {
+        let layers = data
+            .layers
+            .iter()
+            .map(|l| Layer::from_data(arena, l))
+            .collect();
+        MLP { layers }
+    }
+
+    /// Build a network from consecutive layer sizes, e.g. `&[3, 4, 1]` for a
+    /// 3-input, 4-hidden, 1-output net, with every layer using `activation`
+    /// and Xavier/He-initialized weights (see [`Layer::random`]).
+    pub fn random(
+        arena: &mut GraphArena<T>,
+        sizes: &[usize],
+        activation: Activation<T>,
+        rng: &mut impl Rng,
+    ) -> Self {
+        let layers = sizes
+            .windows(2)
+            .map(|w| Layer::random(arena, w[0], w[1], activation, rng))
+            .collect();
+        MLP::new(layers)
+    }
+}
+
+/// A serializable snapshot of an [`MLP`]; see [`NeuronData`].
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct MlpData<T> {
+    pub layers: Vec<LayerData<T>>,
+}
+
+impl<T: Float + Copy> Module<T> for MLP<T> {
+    fn forward(&mut self, arena: &mut GraphArena<T>, inputs: &[usize]) -> Vec<usize> {
         let mut out = inputs.to_vec();
         for layer in &mut self.layers {
             out = layer.forward(arena, &out);
         }
         out
     }
+
+    fn parameters(&self) -> Vec<usize> {
+        self.layers.iter().flat_map(|l| l.parameters()).collect()
+    }
 }
@@ -0,0 +1,88 @@
+use crate::arena::GraphArena;
+use num_traits::Float;
+use std::collections::HashMap;
+
+/// Updates a set of persistent parameter nodes in place from their
+/// accumulated gradients, after a [`GraphArena::backward`] call.
+pub trait Optimizer<T: Float + Copy> {
+    /// Apply one update step to `params` (node IDs returned by
+    /// [`crate::mlp::Module::parameters`]), reading `arena.nodes[id].grad`
+    /// and writing the new value to `arena.nodes[id].data`.
+    fn step(&mut self, arena: &mut GraphArena<T>, params: &[usize]);
+}
+
+/// Stochastic gradient descent with classical momentum, keyed per parameter
+/// node ID so the same optimizer instance can be reused across steps.
+pub struct Sgd<T> {
+    pub lr: T,
+    pub momentum: T,
+    velocity: HashMap<usize, T>,
+}
+
+impl<T: Float + Copy> Sgd<T> {
+    pub fn new(lr: T, momentum: T) -> Self {
+        Sgd {
+            lr,
+            momentum,
+            velocity: HashMap::new(),
+        }
+    }
+}
+
+impl<T: Float + Copy> Optimizer<T> for Sgd<T> {
+    fn step(&mut self, arena: &mut GraphArena<T>, params: &[usize]) {
+        for &id in params {
+            let grad = arena.nodes[id].grad;
+            let v = self.velocity.entry(id).or_insert_with(T::zero);
+            *v = self.momentum * *v + grad;
+            arena.nodes[id].data = arena.nodes[id].data - self.lr * *v;
+        }
+    }
+}
+
+/// Adam, maintaining per-parameter first and second moment estimates keyed
+/// by node ID.
+pub struct Adam<T> {
+    pub lr: T,
+    pub betas: (T, T),
+    pub eps: T,
+    step: i32,
+    m: HashMap<usize, T>,
+    v: HashMap<usize, T>,
+}
+
+impl<T: Float + Copy> Adam<T> {
+    pub fn new(lr: T, betas: (T, T), eps: T) -> Self {
+        Adam {
+            lr,
+            betas,
+            eps,
+            step: 0,
+            m: HashMap::new(),
+            v: HashMap::new(),
+        }
+    }
+}
+
+impl<T: Float + Copy> Optimizer<T> for Adam<T> {
+    fn step(&mut self, arena: &mut GraphArena<T>, params: &[usize]) {
+        self.step += 1;
+        let t = T::from(self.step).expect("step count fits in T");
+        let (b1, b2) = self.betas;
+        let bias_correction1 = T::one() - b1.powf(t);
+        let bias_correction2 = T::one() - b2.powf(t);
+
+        for &id in params {
+            let grad = arena.nodes[id].grad;
+            let m = self.m.entry(id).or_insert_with(T::zero);
+            let v = self.v.entry(id).or_insert_with(T::zero);
+            *m = b1 * *m + (T::one() - b1) * grad;
+            *v = b2 * *v + (T::one() - b2) * grad * grad;
+
+            let m_hat = *m / bias_correction1;
+            let v_hat = *v / bias_correction2;
+            arena.nodes[id].data =
+                arena.nodes[id].data - self.lr * m_hat / (v_hat.sqrt() + self.eps);
+        }
+    }
+}
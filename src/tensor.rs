@@ -0,0 +1,188 @@
+use crate::arena::GraphArena;
+use num_traits::Float;
+use std::marker::PhantomData;
+
+/// A row-major 2-D view over scalar nodes already living in a `GraphArena`.
+///
+/// Every op below is built out of the arena's existing scalar primitives
+/// (`add`, `mul`, `relu`, `tanh`, `div`), so the arena's own `backward` pass already implements the
+/// matrix chain rule (`dA = dC*B^T`, `dB = A^T*dC` for matmul, broadcast
+/// gradients summed back along the broadcast axis, reductions fanned out
+/// uniformly) without any extra bookkeeping here.
+#[derive(Clone, Debug)]
+pub struct Tensor<T: Float + Copy> {
+    pub shape: (usize, usize),
+    pub ids: Vec<usize>,
+    _marker: PhantomData<T>,
+}
+
+impl<T: Float + Copy> Tensor<T> {
+    /// Allocate a new tensor as fresh input nodes, filled row-major from `data`.
+    pub fn from_data(arena: &mut GraphArena<T>, rows: usize, cols: usize, data: &[T]) -> Self {
+        assert_eq!(data.len(), rows * cols, "data length must match shape");
+        let ids = data.iter().map(|&d| arena.input(d)).collect();
+        Tensor {
+            shape: (rows, cols),
+            ids,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Wrap node ids already living in `arena` as a tensor view, without
+    /// allocating any new nodes. Used by [`crate::mlp::Layer::forward`] to
+    /// treat a layer's per-neuron weight/bias nodes as one matrix/row so the
+    /// affine part of the layer can be expressed as `matmul` + broadcast
+    /// `add` instead of a scalar mul/add loop per neuron.
+    pub fn from_ids(shape: (usize, usize), ids: Vec<usize>) -> Self {
+        assert_eq!(ids.len(), shape.0 * shape.1, "ids length must match shape");
+        Tensor {
+            shape,
+            ids,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.shape.0
+    }
+
+    pub fn cols(&self) -> usize {
+        self.shape.1
+    }
+
+    /// Transpose view: reorders the same node ids, allocates no new nodes.
+    pub fn transpose(&self) -> Tensor<T> {
+        let (rows, cols) = self.shape;
+        let mut ids = Vec::with_capacity(rows * cols);
+        for c in 0..cols {
+            for r in 0..rows {
+                ids.push(self.at(r, c));
+            }
+        }
+        Tensor {
+            shape: (cols, rows),
+            ids,
+            _marker: PhantomData,
+        }
+    }
+
+    fn at(&self, r: usize, c: usize) -> usize {
+        self.ids[r * self.shape.1 + c]
+    }
+
+    /// Index as if broadcast to `(r, c)`: a size-1 axis always reads index 0.
+    fn broadcast_at(&self, r: usize, c: usize) -> usize {
+        let rr = if self.shape.0 == 1 { 0 } else { r };
+        let cc = if self.shape.1 == 1 { 0 } else { c };
+        self.at(rr, cc)
+    }
+
+    fn broadcast_shape(&self, other: &Tensor<T>) -> (usize, usize) {
+        let rows = self.shape.0.max(other.shape.0);
+        let cols = self.shape.1.max(other.shape.1);
+        let fits = |shape: (usize, usize)| {
+            (shape.0 == rows || shape.0 == 1) && (shape.1 == cols || shape.1 == 1)
+        };
+        assert!(
+            fits(self.shape) && fits(other.shape),
+            "add: shapes {:?} and {:?} cannot be broadcast together",
+            self.shape,
+            other.shape
+        );
+        (rows, cols)
+    }
+
+    /// Matrix product: `self` is `(m, k)`, `other` is `(k, n)`, result is `(m, n)`.
+    pub fn matmul(&self, arena: &mut GraphArena<T>, other: &Tensor<T>) -> Tensor<T> {
+        assert_eq!(
+            self.shape.1, other.shape.0,
+            "matmul: inner dimensions must match"
+        );
+        let (m, k, n) = (self.shape.0, self.shape.1, other.shape.1);
+        let mut ids = Vec::with_capacity(m * n);
+        for r in 0..m {
+            for c in 0..n {
+                let mut acc = arena.mul(self.at(r, 0), other.at(0, c));
+                for i in 1..k {
+                    let prod = arena.mul(self.at(r, i), other.at(i, c));
+                    acc = arena.add(acc, prod);
+                }
+                ids.push(acc);
+            }
+        }
+        Tensor {
+            shape: (m, n),
+            ids,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Elementwise add with row/column broadcasting: an operand with a
+    /// size-1 axis is broadcast across that axis of the other operand.
+    pub fn add(&self, arena: &mut GraphArena<T>, other: &Tensor<T>) -> Tensor<T> {
+        let (rows, cols) = self.broadcast_shape(other);
+        let mut ids = Vec::with_capacity(rows * cols);
+        for r in 0..rows {
+            for c in 0..cols {
+                ids.push(arena.add(self.broadcast_at(r, c), other.broadcast_at(r, c)));
+            }
+        }
+        Tensor {
+            shape: (rows, cols),
+            ids,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Elementwise multiply; shapes must match exactly.
+    pub fn mul(&self, arena: &mut GraphArena<T>, other: &Tensor<T>) -> Tensor<T> {
+        assert_eq!(self.shape, other.shape, "mul: shapes must match");
+        let ids = self
+            .ids
+            .iter()
+            .zip(&other.ids)
+            .map(|(&a, &b)| arena.mul(a, b))
+            .collect();
+        Tensor {
+            shape: self.shape,
+            ids,
+            _marker: PhantomData,
+        }
+    }
+
+    /// ReLU applied componentwise.
+    pub fn relu(&self, arena: &mut GraphArena<T>) -> Tensor<T> {
+        let ids = self.ids.iter().map(|&a| arena.relu(a)).collect();
+        Tensor {
+            shape: self.shape,
+            ids,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Tanh applied componentwise.
+    pub fn tanh(&self, arena: &mut GraphArena<T>) -> Tensor<T> {
+        let ids = self.ids.iter().map(|&a| arena.tanh(a)).collect();
+        Tensor {
+            shape: self.shape,
+            ids,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Sum every element down to a single scalar node.
+    pub fn sum(&self, arena: &mut GraphArena<T>) -> usize {
+        let mut acc = self.ids[0];
+        for &id in &self.ids[1..] {
+            acc = arena.add(acc, id);
+        }
+        acc
+    }
+
+    /// Mean of every element as a single scalar node.
+    pub fn mean(&self, arena: &mut GraphArena<T>) -> usize {
+        let total = self.sum(arena);
+        let count = arena.input(T::from(self.ids.len()).expect("element count fits in T"));
+        arena.div(total, count)
+    }
+}
@@ -0,0 +1,450 @@
+//! Behavior tests for the two autodiff engines (`engine::Value` and
+//! `arena::GraphArena`), checkpointed backward, and the neuroevolution
+//! subsystem (`genetic`). These are correctness-critical and easy to get
+//! subtly wrong, so this module exercises the properties their own doc
+//! comments promise: hand-computed gradients, checkpointed/plain
+//! equivalence, `connect`'s cycle rejection and rewired traversal order,
+//! and reproducible evolution given a seeded RNG.
+
+use crate::arena::{ConnectError, GraphArena};
+use crate::engine::Value;
+use crate::genetic::{evolve, gaussian_mutation, roulette, uniform_crossover};
+use crate::mlp::{Activation, Module, Neuron, MLP};
+use crate::optim::{Adam, Optimizer, Sgd};
+use crate::tensor::Tensor;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+// ---- engine::Value -------------------------------------------------------
+
+#[test]
+fn value_backward_matches_hand_computed_gradients() {
+    // f(x, y) = x*y + x  =>  df/dx = y+1, df/dy = x
+    let x = Value::new(3.0_f64);
+    let y = Value::new(-2.0_f64);
+    let xy = &x * &y;
+    let f = &xy + &x;
+    f.backward();
+    assert_eq!(x.grad(), -1.0);
+    assert_eq!(y.grad(), 3.0);
+}
+
+#[test]
+fn value_sub_div_neg_gradients() {
+    // f(x, y) = -(x - y) / y  =>  df/dx = -1/y, df/dy = x/y^2
+    let x = Value::new(6.0_f64);
+    let y = Value::new(3.0_f64);
+    let diff = &x - &y;
+    let negated = -&diff;
+    let f = &negated / &y;
+    f.backward();
+    assert!((x.grad() - (-1.0 / y.get())).abs() < 1e-9);
+    let expected_dy = x.get() / (y.get() * y.get());
+    assert!((y.grad() - expected_dy).abs() < 1e-9);
+}
+
+#[test]
+fn value_relu_tanh_and_powf_gradients() {
+    let x = Value::new(-1.0_f64);
+    let r = x.relu();
+    r.backward();
+    assert_eq!(x.grad(), 0.0);
+
+    let x = Value::new(0.5_f64);
+    let t = x.tanh();
+    t.backward();
+    let y = t.get();
+    assert!((x.grad() - (1.0 - y * y)).abs() < 1e-9);
+
+    let x = Value::new(3.0_f64);
+    let p = x.powf(3.0);
+    p.backward();
+    assert!((p.get() - 27.0).abs() < 1e-9);
+    assert!((x.grad() - 3.0 * 3.0_f64.powf(2.0)).abs() < 1e-9);
+}
+
+// ---- tensor::Tensor --------------------------------------------------------
+
+#[test]
+fn tensor_matmul_matches_hand_computed_product() {
+    let mut arena = GraphArena::<f64>::new();
+    // [[1, 2],   [[5, 6],     [[19, 22],
+    //  [3, 4]] x  [7, 8]]  =   [43, 50]]
+    let a = Tensor::from_data(&mut arena, 2, 2, &[1.0, 2.0, 3.0, 4.0]);
+    let b = Tensor::from_data(&mut arena, 2, 2, &[5.0, 6.0, 7.0, 8.0]);
+    let c = a.matmul(&mut arena, &b);
+    assert_eq!(c.shape, (2, 2));
+    let data: Vec<f64> = c.ids.iter().map(|&id| arena.nodes[id].data).collect();
+    assert_eq!(data, vec![19.0, 22.0, 43.0, 50.0]);
+}
+
+#[test]
+fn tensor_add_broadcasts_row_and_column() {
+    let mut arena = GraphArena::<f64>::new();
+    let a = Tensor::from_data(&mut arena, 2, 2, &[1.0, 2.0, 3.0, 4.0]);
+
+    // Broadcast a single row across every row of `a`.
+    let row = Tensor::from_data(&mut arena, 1, 2, &[10.0, 20.0]);
+    let plus_row = a.add(&mut arena, &row);
+    let row_data: Vec<f64> = plus_row.ids.iter().map(|&id| arena.nodes[id].data).collect();
+    assert_eq!(row_data, vec![11.0, 22.0, 13.0, 24.0]);
+
+    // Broadcast a single column across every column of `a`.
+    let col = Tensor::from_data(&mut arena, 2, 1, &[100.0, 200.0]);
+    let plus_col = a.add(&mut arena, &col);
+    let col_data: Vec<f64> = plus_col.ids.iter().map(|&id| arena.nodes[id].data).collect();
+    assert_eq!(col_data, vec![101.0, 102.0, 203.0, 204.0]);
+}
+
+#[test]
+fn tensor_mul_sum_and_mean_match_hand_computed_values() {
+    let mut arena = GraphArena::<f64>::new();
+    let a = Tensor::from_data(&mut arena, 1, 3, &[1.0, 2.0, 3.0]);
+    let b = Tensor::from_data(&mut arena, 1, 3, &[4.0, 5.0, 6.0]);
+
+    let prod = a.mul(&mut arena, &b);
+    let prod_data: Vec<f64> = prod.ids.iter().map(|&id| arena.nodes[id].data).collect();
+    assert_eq!(prod_data, vec![4.0, 10.0, 18.0]);
+
+    let sum = prod.sum(&mut arena);
+    assert_eq!(arena.nodes[sum].data, 32.0);
+
+    let mean = prod.mean(&mut arena);
+    assert!((arena.nodes[mean].data - 32.0 / 3.0).abs() < 1e-9);
+}
+
+// ---- optim::Sgd / optim::Adam -----------------------------------------------
+
+#[test]
+fn sgd_step_matches_hand_computed_momentum_update() {
+    let mut arena = GraphArena::<f64>::new();
+    let p = arena.input(1.0);
+    arena.nodes[p].grad = 2.0;
+    let mut sgd = Sgd::new(0.1, 0.9);
+
+    // v1 = 0.9*0 + 2 = 2; data1 = 1.0 - 0.1*2 = 0.8
+    sgd.step(&mut arena, &[p]);
+    assert!((arena.nodes[p].data - 0.8).abs() < 1e-12);
+
+    // Same gradient again: v2 = 0.9*2 + 2 = 3.8; data2 = 0.8 - 0.1*3.8 = 0.42
+    arena.nodes[p].grad = 2.0;
+    sgd.step(&mut arena, &[p]);
+    assert!((arena.nodes[p].data - 0.42).abs() < 1e-12);
+}
+
+#[test]
+fn adam_step_matches_hand_computed_bias_corrected_update() {
+    let mut arena = GraphArena::<f64>::new();
+    let p = arena.input(1.0);
+    arena.nodes[p].grad = 2.0;
+    let mut adam = Adam::new(0.1, (0.9, 0.999), 1e-8);
+
+    // t=1: m = 0.1*2 = 0.2, v = 0.001*4 = 0.004
+    // m_hat = 0.2/0.1 = 2.0, v_hat = 0.004/0.001 = 4.0
+    // update = 0.1 * 2.0 / (sqrt(4.0) + 1e-8) = 0.1 * 2.0 / 2.00000001
+    adam.step(&mut arena, &[p]);
+    let expected = 1.0 - 0.1 * 2.0 / (2.0 + 1e-8);
+    assert!((arena.nodes[p].data - expected).abs() < 1e-9);
+}
+
+// ---- mlp::Activation --------------------------------------------------
+
+#[test]
+fn activation_sigmoid_matches_hand_computed_value() {
+    let mut arena = GraphArena::<f64>::new();
+    let x = arena.input(0.0);
+    let out = Activation::Sigmoid.apply(&mut arena, x);
+    // sigmoid(0) = 0.5
+    assert!((arena.nodes[out].data - 0.5).abs() < 1e-9);
+
+    let mut arena = GraphArena::<f64>::new();
+    let x = arena.input(2.0);
+    let out = Activation::Sigmoid.apply(&mut arena, x);
+    let expected = 1.0 / (1.0 + (-2.0_f64).exp());
+    assert!((arena.nodes[out].data - expected).abs() < 1e-9);
+}
+
+#[test]
+fn activation_leaky_relu_matches_hand_computed_value() {
+    let mut arena = GraphArena::<f64>::new();
+    let x = arena.input(3.0);
+    let out = Activation::LeakyRelu(0.1).apply(&mut arena, x);
+    assert!((arena.nodes[out].data - 3.0).abs() < 1e-9);
+
+    let mut arena = GraphArena::<f64>::new();
+    let x = arena.input(-5.0);
+    let out = Activation::LeakyRelu(0.1).apply(&mut arena, x);
+    // leaky_relu(-5) = 0.1 * -5 = -0.5
+    assert!((arena.nodes[out].data - (-0.5)).abs() < 1e-9);
+}
+
+// ---- mlp random init (Xavier/He) ----------------------------------------
+
+#[test]
+fn neuron_random_xavier_weights_stay_within_bound() {
+    let mut arena = GraphArena::<f64>::new();
+    let mut rng = StdRng::seed_from_u64(11);
+    let (fan_in, fan_out) = (50, 10);
+    let limit = (6.0_f64 / (fan_in + fan_out) as f64).sqrt();
+
+    let neuron = Neuron::random(&mut arena, fan_in, fan_out, Activation::Tanh, &mut rng);
+    for &id in &neuron.weight_ids {
+        assert!(arena.nodes[id].data.abs() <= limit, "weight outside Xavier bound");
+    }
+}
+
+#[test]
+fn neuron_random_he_weights_match_expected_stddev() {
+    let mut arena = GraphArena::<f64>::new();
+    let mut rng = StdRng::seed_from_u64(12);
+    let fan_in = 2000;
+    let expected_std = (2.0_f64 / fan_in as f64).sqrt();
+
+    let neuron = Neuron::random(&mut arena, fan_in, 10, Activation::Relu, &mut rng);
+    let weights: Vec<f64> = neuron.weight_ids.iter().map(|&id| arena.nodes[id].data).collect();
+    let mean = weights.iter().sum::<f64>() / weights.len() as f64;
+    let variance = weights.iter().map(|w| (w - mean).powi(2)).sum::<f64>() / weights.len() as f64;
+    let std = variance.sqrt();
+    // A large sample should land within 10% of the theoretical He stddev.
+    assert!(
+        (std - expected_std).abs() / expected_std < 0.1,
+        "sample std {std} too far from expected {expected_std}"
+    );
+}
+
+// ---- mlp::Module::forward_batch -----------------------------------------
+
+#[test]
+fn forward_batch_reuses_same_weight_nodes_across_rows() {
+    let mut arena = GraphArena::<f64>::new();
+    let mut rng = StdRng::seed_from_u64(13);
+    let mut mlp = MLP::random(&mut arena, &[2, 3, 1], Activation::Tanh, &mut rng);
+    let params_before = mlp.parameters();
+
+    let batch = vec![
+        vec![arena.input(1.0), arena.input(2.0)],
+        vec![arena.input(-1.0), arena.input(0.5)],
+        vec![arena.input(0.0), arena.input(0.0)],
+    ];
+    let node_count_before = arena.nodes.len();
+    let outputs = mlp.forward_batch(&mut arena, &batch);
+
+    assert_eq!(outputs.len(), batch.len());
+    // forward_batch must reuse the same persistent parameter node ids for
+    // every row, not allocate a fresh copy of the weights per row.
+    assert_eq!(mlp.parameters(), params_before);
+    // Only fresh nodes should be the per-row forward computation, not more
+    // parameter nodes.
+    let node_count_after = arena.nodes.len();
+    assert!(node_count_after > node_count_before);
+    for &id in &mlp.parameters() {
+        assert!(id < node_count_before, "parameter node ids must predate the batch forward");
+    }
+}
+
+// ---- mlp serde snapshot ------------------------------------------------
+
+#[test]
+#[cfg(feature = "serialize")]
+fn mlp_round_trips_through_serde_json() {
+    let mut arena = GraphArena::<f64>::new();
+    let mut rng = StdRng::seed_from_u64(3);
+    let mlp = MLP::random(&mut arena, &[2, 3, 1], Activation::Tanh, &mut rng);
+    let data = mlp.to_data(&arena);
+
+    let json = serde_json::to_string(&data).expect("MlpData serializes to JSON");
+    let restored: crate::mlp::MlpData<f64> =
+        serde_json::from_str(&json).expect("MlpData deserializes from JSON");
+
+    assert_eq!(restored, data);
+}
+
+// ---- arena::GraphArena -----------------------------------------------------
+
+#[test]
+fn backward_matches_hand_computed_gradients() {
+    // f(x, y) = x*y + x  =>  df/dx = y+1, df/dy = x
+    let mut arena = GraphArena::<f64>::new();
+    let x = arena.input(3.0);
+    let y = arena.input(-2.0);
+    let xy = arena.mul(x, y);
+    let f = arena.add(xy, x);
+    arena.backward(f);
+    assert_eq!(arena.nodes[x].grad, -1.0);
+    assert_eq!(arena.nodes[y].grad, 3.0);
+}
+
+#[test]
+fn backward_through_relu_and_tanh() {
+    let mut arena = GraphArena::<f64>::new();
+    let x = arena.input(-1.0);
+    let r = arena.relu(x);
+    arena.backward(r);
+    assert_eq!(arena.nodes[x].grad, 0.0);
+
+    let mut arena = GraphArena::<f64>::new();
+    let x = arena.input(0.5);
+    let t = arena.tanh(x);
+    arena.backward(t);
+    let y = arena.nodes[t].data;
+    assert!((arena.nodes[x].grad - (1.0 - y * y)).abs() < 1e-9);
+}
+
+#[test]
+fn backward_checkpointed_matches_plain_backward() {
+    let mut arena = GraphArena::<f64>::new();
+    let mut ids = vec![arena.input(1.0), arena.input(2.0)];
+    let mut acc = ids[0];
+    for i in 1..20 {
+        let x = arena.input(0.1 * i as f64);
+        ids.push(x);
+        let m = arena.mul(acc, x);
+        acc = arena.add(m, ids[1]);
+    }
+    let loss = arena.powf(acc, 2.0);
+
+    arena.backward(loss);
+    let plain_grads: Vec<f64> = ids.iter().map(|&id| arena.nodes[id].grad).collect();
+
+    arena.backward_checkpointed_with_spacing(loss, 3);
+    let checkpointed_grads: Vec<f64> = ids.iter().map(|&id| arena.nodes[id].grad).collect();
+
+    for (p, c) in plain_grads.iter().zip(&checkpointed_grads) {
+        assert!((p - c).abs() < 1e-9, "{p} != {c}");
+    }
+}
+
+#[test]
+fn connect_rejects_cycles() {
+    let mut arena = GraphArena::<f64>::new();
+    let a = arena.input(1.0);
+    // b and c are Op::Custom, so their arity is unlimited; only the cycle
+    // check should be able to reject wiring c as a parent of b.
+    let b = arena.apply("id", vec![a], |xs| xs[0], |grad, _xs, _out| vec![grad]);
+    let c = arena.apply("id", vec![b], |xs| xs[0], |grad, _xs, _out| vec![grad]);
+    // c already depends on b; wiring c as a parent of b would close the
+    // cycle b -> c -> b.
+    assert!(matches!(arena.connect(b, c), Err(ConnectError::Cycle(_))));
+}
+
+#[test]
+fn connect_rewires_backward_for_custom_ops() {
+    let mut arena = GraphArena::<f64>::new();
+    let a = arena.input(2.0);
+    let b = arena.input(3.0);
+    let sum = arena.apply(
+        "sum",
+        vec![a, b],
+        |xs| xs.iter().copied().sum(),
+        |grad, xs, _out| xs.iter().map(|_| grad).collect(),
+    );
+    let c = arena.input(10.0);
+    arena
+        .connect(sum, c)
+        .expect("no cycle, Custom has unlimited arity");
+
+    arena.backward(sum);
+    // Op::Custom's backward returns `grad` for every parent, so the rewired
+    // third parent must pick up a gradient too, not just a and b.
+    assert_eq!(arena.nodes[a].grad, 1.0);
+    assert_eq!(arena.nodes[b].grad, 1.0);
+    assert_eq!(arena.nodes[c].grad, 1.0);
+}
+
+#[test]
+fn connect_onto_builtin_op_is_rejected_at_connect_time() {
+    let mut arena = GraphArena::<f64>::new();
+    let a = arena.input(2.0);
+    let b = arena.input(3.0);
+    let sum = arena.add(a, b);
+    let c = arena.input(10.0);
+
+    let err = arena.connect(sum, c).unwrap_err();
+    assert_eq!(
+        err,
+        ConnectError::FixedArity {
+            op_name: "Add",
+            max_parents: 2,
+        }
+    );
+    // Rejected before mutating anything.
+    assert_eq!(arena.nodes[sum].parents, vec![a, b]);
+}
+
+#[test]
+fn chromosome_round_trips_through_from_chromosome() {
+    let mut arena = GraphArena::<f64>::new();
+    let mut rng = StdRng::seed_from_u64(42);
+    let mut mlp = MLP::random(&mut arena, &[2, 3, 1], Activation::Tanh, &mut rng);
+    let original = mlp.chromosome(&arena);
+
+    let mut genes = original.clone();
+    for g in genes.iter_mut() {
+        *g += 1.0;
+    }
+    mlp.from_chromosome(&mut arena, &genes);
+    assert_eq!(mlp.chromosome(&arena), genes);
+    assert_ne!(genes, original);
+}
+
+#[test]
+fn roulette_selection_is_reproducible_for_a_fixed_seed() {
+    let fitnesses = [1.0_f64, 2.0, 3.0, 4.0];
+    let mut rng_a = StdRng::seed_from_u64(7);
+    let mut rng_b = StdRng::seed_from_u64(7);
+    let picks_a: Vec<usize> = (0..50).map(|_| roulette(&fitnesses, &mut rng_a)).collect();
+    let picks_b: Vec<usize> = (0..50).map(|_| roulette(&fitnesses, &mut rng_b)).collect();
+    assert_eq!(picks_a, picks_b);
+    assert!(picks_a.iter().all(|&i| i < fitnesses.len()));
+}
+
+#[test]
+fn uniform_crossover_preserves_length_and_gene_pool() {
+    let mut rng = StdRng::seed_from_u64(1);
+    let a = vec![1.0_f64, 2.0, 3.0, 4.0];
+    let b = vec![10.0_f64, 20.0, 30.0, 40.0];
+    let (child_a, child_b) = uniform_crossover(&a, &b, &mut rng);
+    assert_eq!(child_a.len(), a.len());
+    assert_eq!(child_b.len(), b.len());
+    for i in 0..a.len() {
+        assert!(child_a[i] == a[i] || child_a[i] == b[i]);
+        // Each gene comes from whichever parent child_a did *not* take.
+        assert_ne!(child_a[i], child_b[i]);
+    }
+}
+
+#[test]
+fn gaussian_mutation_is_reproducible_for_a_fixed_seed() {
+    let genes_template = vec![0.0_f64; 100];
+    let mut rng_a = StdRng::seed_from_u64(99);
+    let mut rng_b = StdRng::seed_from_u64(99);
+    let mut genes_a = genes_template.clone();
+    let mut genes_b = genes_template.clone();
+    gaussian_mutation(&mut genes_a, 0.3, 1.0, &mut rng_a);
+    gaussian_mutation(&mut genes_b, 0.3, 1.0, &mut rng_b);
+    assert_eq!(genes_a, genes_b);
+    assert!(genes_a.iter().any(|&g| g != 0.0), "rate 0.3 should mutate something");
+}
+
+#[test]
+fn evolve_preserves_population_size_and_node_count() {
+    let mut arena = GraphArena::<f64>::new();
+    let mut rng = StdRng::seed_from_u64(5);
+    let mut population: Vec<MLP<f64>> = (0..6)
+        .map(|_| MLP::random(&mut arena, &[2, 3, 1], Activation::Tanh, &mut rng))
+        .collect();
+    let fitnesses = vec![1.0, 0.0, 2.0, 0.5, 3.0, 1.5];
+
+    let population_size = population.len();
+    let node_count_before = arena.nodes.len();
+    let original_genes: Vec<Vec<f64>> = population.iter().map(|m| m.chromosome(&arena)).collect();
+
+    evolve(&mut population, &mut arena, &fitnesses, 0.1, 0.5, &mut rng);
+
+    assert_eq!(population.len(), population_size);
+    // evolve must rewrite genes into the existing persistent nodes rather
+    // than allocating a fresh set per generation.
+    assert_eq!(arena.nodes.len(), node_count_before);
+    let new_genes: Vec<Vec<f64>> = population.iter().map(|m| m.chromosome(&arena)).collect();
+    assert_ne!(new_genes, original_genes);
+}